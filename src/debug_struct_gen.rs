@@ -0,0 +1,159 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHdest WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl_generator::{Registry, Cmd};
+use gl_generator::generators;
+
+use std::io;
+
+use struct_gen::{
+    gen_parameters, gen_return_type, write_enum_groups, write_enums, write_fnptr_struct_def,
+    write_header, write_loaded_fn_ptrs, write_metaloadfn_prelude, write_panicking_fns,
+    write_struct, write_type_aliases,
+};
+
+/// Like `StructGenerator`, but every generated wrapper fn prints the command name and its
+/// (typed) arguments before the call, and checks `glGetError` after the call, printing the
+/// decoded error name if one was raised.
+#[allow(missing_copy_implementations)]
+pub struct DebugStructGenerator;
+
+impl generators::Generator for DebugStructGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(write_header(dest));
+        try!(write_type_aliases(registry, dest));
+        try!(write_enums(registry, dest));
+        try!(write_fnptr_struct_def(dest));
+        try!(write_panicking_fns(registry, dest));
+        try!(write_struct(registry, dest));
+        try!(write_debug_impl(registry, dest));
+        try!(write_enum_groups(registry, dest));
+        Ok(())
+    }
+}
+
+/// Creates the `impl` of the structure created by `write_struct`, instrumenting every
+/// generated `unsafe fn` with a `println!` of the command and its arguments, followed by a
+/// `glGetError` check.
+fn write_debug_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    try!(writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol using a custom load function. This allows for the
+            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{",
+                  api = generators::gen_struct_name(registry.api)));
+
+    try!(write_metaloadfn_prelude(dest));
+
+    try!(writeln!(dest,
+                  "                {api}::load_with_metaloadfn(&mut metaloadfn)
+            }}
+
+            #[inline(never)]
+            fn load_with_metaloadfn(metaloadfn: &mut FnMut(&'static str, &[&'static str]) -> *const __gl_imports::raw::c_void) -> {api} {{
+
+                {api} {{
+                    ptrs: {api}FnPtrs {{",
+                  api = generators::gen_struct_name(registry.api)));
+
+    try!(write_loaded_fn_ptrs(registry, dest));
+
+    writeln!(dest, "}},")?;
+
+    try!(writeln!(dest, "_priv: ()"));
+
+    try!(writeln!(
+        dest,
+        "}}
+        }}"
+    ));
+
+    for cmd in &registry.cmds {
+        try!(write_debug_fn(cmd, registry, dest));
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        unsafe impl __gl_imports::Send for {api} {{}}",
+        api = generators::gen_struct_name(registry.api)
+    )
+}
+
+/// Writes a single instrumented wrapper `unsafe fn` for `cmd`.
+fn write_debug_fn<W>(cmd: &Cmd, registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let idents = gen_parameters(cmd, registry, true, false);
+    let debug_fmt = idents
+        .iter()
+        .map(|ident| format!("{}: {{:?}}", ident))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // The `GetError` wrapper itself (the command whose ident is exactly `GetError`) must not
+    // check for errors after calling itself, or it would recurse forever.
+    let error_check = if cmd.proto.ident == "GetError" {
+        format!("")
+    } else {
+        format!(
+            "let __error = __gl_imports::mem::transmute::<_, extern \"system\" fn() -> types::GLenum>(self.ptrs.GetError.f)();
+            if __error != 0 {{
+                let __error_name = match __error {{
+                    0x0500 => \"INVALID_ENUM\",
+                    0x0501 => \"INVALID_VALUE\",
+                    0x0502 => \"INVALID_OPERATION\",
+                    0x0503 => \"STACK_OVERFLOW\",
+                    0x0504 => \"STACK_UNDERFLOW\",
+                    0x0505 => \"OUT_OF_MEMORY\",
+                    0x0506 => \"INVALID_FRAMEBUFFER_OPERATION\",
+                    _ => \"UNKNOWN_ERROR\",
+                }};
+                println!(\"[gl] {name}: {{}} (0x{{:04X}})\", __error_name, __error);
+            }}",
+            name = cmd.proto.ident,
+        )
+    };
+
+    writeln!(dest,
+        "#[allow(non_snake_case, unused_variables, dead_code)]
+        #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+            println!(\"[gl] {name}({debug_fmt})\", {idents}); \
+            let __result = __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
+                (self.ptrs.{name}.f)({idents}); \
+            {error_check} \
+            __result \
+        }}",
+        name = cmd.proto.ident,
+        params = gen_parameters(cmd, registry, true, true).join(", "),
+        typed_params = gen_parameters(cmd, registry, false, true).join(", "),
+        return_suffix = gen_return_type(cmd, registry),
+        idents = idents.join(", "),
+        debug_fmt = debug_fmt,
+        error_check = error_check,
+    )
+}
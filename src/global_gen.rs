@@ -0,0 +1,141 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHdest WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl_generator::Registry;
+use gl_generator::generators;
+
+use std::io;
+
+use struct_gen::{
+    gen_parameters, gen_return_type, write_enum_groups, write_enums, write_fnptr_struct_def,
+    write_header, write_loaded_fn_ptrs, write_metaloadfn_prelude, write_panicking_fns,
+    write_type_aliases,
+};
+
+/// Like `StructGenerator`, but stores the loaded function pointers in a single process-wide
+/// global instead of requiring the caller to thread an API struct through every call, and
+/// emits free `pub unsafe fn` wrappers that dispatch through it.
+#[allow(missing_copy_implementations)]
+pub struct GlobalGenerator;
+
+impl generators::Generator for GlobalGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(write_header(dest));
+        try!(write_type_aliases(registry, dest));
+        try!(write_enums(registry, dest));
+        try!(write_fnptr_struct_def(dest));
+        try!(write_panicking_fns(registry, dest));
+        try!(write_global_struct(registry, dest));
+        try!(write_global_load_fn(registry, dest));
+        try!(write_global_fns(registry, dest));
+        try!(write_enum_groups(registry, dest));
+        Ok(())
+    }
+}
+
+/// Creates the structure that stores the `FnPtr` of every command, and a single process-wide
+/// `static mut` instance of it, initialized in the "not yet loaded" state.
+fn write_global_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    try!(writeln!(
+        dest,
+        "#[allow(non_camel_case_types, non_snake_case, dead_code)]
+        struct FnPtrs {{"
+    ));
+
+    for cmd in &registry.cmds {
+        try!(writeln!(dest, "{name}: FnPtr,", name = cmd.proto.ident));
+    }
+
+    try!(writeln!(dest, "}}"));
+    try!(writeln!(dest, ""));
+
+    try!(writeln!(dest, "#[allow(non_upper_case_globals)]"));
+    try!(writeln!(dest, "static mut GL: FnPtrs = FnPtrs {{"));
+
+    for cmd in &registry.cmds {
+        try!(writeln!(
+            dest,
+            "{name}: FnPtr {{ f: missing_fn_panic as *const __gl_imports::raw::c_void, is_loaded: false }},",
+            name = cmd.proto.ident,
+        ))
+    }
+
+    writeln!(dest, "}};")
+}
+
+/// Creates the top-level `load_with` that populates the process-wide global.
+fn write_global_load_fn<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    try!(writeln!(
+        dest,
+        "/// Load each OpenGL symbol using a custom load function, populating the process-wide
+        /// global used by the free functions in this module. This allows for the use of
+        /// functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+        ///
+        /// ~~~ignore
+        /// gl::load_with(|s| glfw.get_proc_address(s));
+        /// ~~~
+        #[allow(dead_code, unused_variables)]
+        pub fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{"
+    ));
+
+    try!(write_metaloadfn_prelude(dest));
+
+    try!(writeln!(
+        dest,
+        "            unsafe {{
+                GL = FnPtrs {{"
+    ));
+
+    try!(write_loaded_fn_ptrs(registry, dest));
+
+    writeln!(
+        dest,
+        "}};
+            }}
+        }}"
+    )
+}
+
+/// Creates the free `pub unsafe fn` wrapper for each command, dispatching through the
+/// process-wide global.
+fn write_global_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for cmd in &registry.cmds {
+        try!(writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline] pub unsafe fn {name}({params}) -> {return_suffix} {{ \
+                __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
+                    (GL.{name}.f)({idents}) \
+            }}",
+            name = cmd.proto.ident,
+            params = gen_parameters(cmd, &registry, true, true).join(", "),
+            typed_params = gen_parameters(cmd, &registry, false, true).join(", "),
+            return_suffix = gen_return_type(cmd, &registry),
+            idents = gen_parameters(cmd, &registry, true, false).join(", "),
+        ))
+    }
+
+    Ok(())
+}
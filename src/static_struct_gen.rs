@@ -0,0 +1,181 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHdest WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gl_generator::{Registry, Cmd};
+use gl_generator::generators;
+
+use std::io;
+
+use struct_gen::{
+    gen_parameters, gen_return_type, group_backing_type, write_enum_groups, write_enums,
+    write_header, write_type_aliases,
+};
+
+/// Like `StructGenerator`, but for platforms where the GL implementation is statically linked
+/// instead of loaded at runtime. Commands are declared in an `extern "system"` block under
+/// their real symbol names, and the wrapper fns forward to them directly rather than going
+/// through a loaded `FnPtr`.
+#[allow(missing_copy_implementations)]
+pub struct StaticStructGenerator;
+
+impl generators::Generator for StaticStructGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        try!(write_header(dest));
+        try!(write_type_aliases(registry, dest));
+        try!(write_enums(registry, dest));
+        try!(write_extern_block(registry, dest));
+        try!(write_static_struct(registry, dest));
+        try!(write_static_impl(registry, dest));
+        try!(write_enum_groups(registry, dest));
+        Ok(())
+    }
+}
+
+/// Declares the statically linked C symbols for each command.
+fn write_extern_block<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest, "#[allow(non_snake_case)]")?;
+    writeln!(dest, "extern \"system\" {{")?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "    fn {symbol}({params}) -> {return_suffix};",
+            symbol = generators::gen_symbol_name(registry.api, &cmd.proto.ident),
+            params = gen_extern_params(cmd).join(", "),
+            return_suffix = cmd.proto.ty,
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the zero-sized structure that exposes the statically linked bindings.
+fn write_static_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        #[derive(Copy, Clone)]
+        pub struct {api} {{
+            _priv: (),
+        }}
+        ",
+        api = generators::gen_struct_name(registry.api)
+    )
+}
+
+/// Creates the `impl` of the structure created by `write_static_struct`.
+fn write_static_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    try!(writeln!(
+        dest,
+        "impl {api} {{
+            /// Statically linked symbols are always available, so this just returns the
+            /// (zero-sized) struct. `loadfn` is accepted purely for API compatibility with
+            /// the dynamically-loaded generators.
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with<F>(loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                {api} {{ _priv: () }}
+            }}",
+        api = generators::gen_struct_name(registry.api)
+    ));
+
+    for cmd in &registry.cmds {
+        try!(writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+                {wrap_open}{symbol}({args}){wrap_close} \
+            }}",
+            name = cmd.proto.ident,
+            params = gen_parameters(cmd, &registry, true, true).join(", "),
+            return_suffix = gen_return_type(cmd, &registry),
+            symbol = generators::gen_symbol_name(registry.api, &cmd.proto.ident),
+            args = gen_call_args(cmd, &registry).join(", "),
+            wrap_open = gen_return_wrap_open(cmd, &registry),
+            wrap_close = gen_return_wrap_close(cmd, &registry),
+        ))
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        unsafe impl __gl_imports::Send for {api} {{}}",
+        api = generators::gen_struct_name(registry.api)
+    )
+}
+
+/// Generates the `extern "system"` parameter list using the raw C ABI types, rather than
+/// this crate's typed enum-group newtypes.
+fn gen_extern_params(cmd: &Cmd) -> Vec<String> {
+    cmd.params
+        .iter()
+        .map(|binding| format!("{}: {}", binding.ident, binding.ty))
+        .collect()
+}
+
+/// Generates the argument list used to call into the `extern "system"` block from a typed
+/// wrapper fn, unwrapping enum-group newtypes back to their raw representation.
+///
+/// Only unwraps a `.0` when `gen_parameters` actually substituted the enum-group newtype for
+/// this parameter (i.e. the parameter's raw type is exactly the group's backing type) —
+/// otherwise the parameter was left as its raw FFI type (e.g. a pointer/array out-param, or a
+/// differently-typed value the group merely annotates) and must be passed through as-is.
+fn gen_call_args(cmd: &Cmd, registry: &Registry) -> Vec<String> {
+    cmd.params
+        .iter()
+        .map(|binding| {
+            let substituted = binding.group
+                .as_ref()
+                .and_then(|group| registry.groups.get(group))
+                .map_or(false, |group| binding.ty == group_backing_type(&group.ident));
+
+            if substituted {
+                format!("{}.0", binding.ident)
+            } else {
+                binding.ident.to_string()
+            }
+        })
+        .collect()
+}
+
+/// The opening half of the expression that wraps a raw extern call's return value in its
+/// `enums::<Group>` newtype, if `cmd` declares one. Paired with `gen_return_wrap_close`.
+fn gen_return_wrap_open(cmd: &Cmd, registry: &Registry) -> String {
+    cmd.proto
+        .group
+        .as_ref()
+        .and_then(|group| registry.groups.get(group).map(|group| format!("enums::{}(", group.ident)))
+        .unwrap_or(format!(""))
+}
+
+/// The closing half of the expression opened by `gen_return_wrap_open`.
+fn gen_return_wrap_close(cmd: &Cmd, registry: &Registry) -> String {
+    cmd.proto
+        .group
+        .as_ref()
+        .and_then(|group| registry.groups.get(group).map(|_| format!(")")))
+        .unwrap_or(format!(""))
+}
@@ -39,7 +39,7 @@ impl generators::Generator for StructGenerator {
 
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
 ///  bindings.
-fn write_header<W>(dest: &mut W) -> io::Result<()>
+pub(crate) fn write_header<W>(dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -58,7 +58,7 @@ where
 /// Creates a `types` module which contains all the type aliases.
 ///
 /// See also `generators::gen_types`.
-fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+pub(crate) fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -76,7 +76,7 @@ where
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+pub(crate) fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -88,7 +88,7 @@ where
 }
 
 /// Creates a `FnPtr` structure which contains the store for a single binding.
-fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
+pub(crate) fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -133,7 +133,7 @@ where
 /// Creates a `panicking` module which contains one function per GL command.
 ///
 /// These functions are the mocks that are called if the real function could not be loaded.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+pub(crate) fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -150,7 +150,7 @@ where
 /// Creates a structure which stores all the `FnPtr` of the bindings.
 ///
 /// The name of the struct corresponds to the namespace.
-fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+pub(crate) fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -191,48 +191,46 @@ where
     Ok(())
 }
 
-/// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+/// Writes the `do_metaloadfn`/`metaloadfn` closure that every `load_with` variant builds on
+/// top of: it tries a command's primary symbol name first, then falls back through its
+/// aliases. Expects a `mut loadfn: F` (`F: FnMut(&'static str) -> *const __gl_imports::raw::c_void`)
+/// to already be in scope, and leaves a `metaloadfn` closure of type
+/// `FnMut(&'static str, &[&'static str]) -> *const __gl_imports::raw::c_void` in scope for the
+/// caller to use.
+pub(crate) fn write_metaloadfn_prelude<W>(dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
-    try!(writeln!(dest,
-                  "impl {api} {{
-            /// Load each OpenGL symbol using a custom load function. This allows for the
-            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
-            ///
-            /// ~~~ignore
-            /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
-            /// ~~~
-            #[allow(dead_code, unused_variables)]
-            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
-                #[inline(never)]
-                fn do_metaloadfn(loadfn: &mut FnMut(&'static str) -> *const __gl_imports::raw::c_void,
-                                 symbol: &'static str,
-                                 symbols: &[&'static str])
-                                 -> *const __gl_imports::raw::c_void {{
-                    let mut ptr = loadfn(symbol);
-                    if ptr.is_null() {{
-                        for &sym in symbols {{
-                            ptr = loadfn(sym);
-                            if !ptr.is_null() {{ break; }}
-                        }}
-                    }}
-                    ptr
+    writeln!(
+        dest,
+        "#[inline(never)]
+        fn do_metaloadfn(loadfn: &mut FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                         symbol: &'static str,
+                         symbols: &[&'static str])
+                         -> *const __gl_imports::raw::c_void {{
+            let mut ptr = loadfn(symbol);
+            if ptr.is_null() {{
+                for &sym in symbols {{
+                    ptr = loadfn(sym);
+                    if !ptr.is_null() {{ break; }}
                 }}
-                let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
-                    do_metaloadfn(&mut loadfn, symbol, symbols)
-                }};
-                {api}::load_with_metaloadfn(&mut metaloadfn)
             }}
+            ptr
+        }}
+        let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
+            do_metaloadfn(&mut loadfn, symbol, symbols)
+        }};"
+    )
+}
 
-            #[inline(never)]
-            fn load_with_metaloadfn(metaloadfn: &mut FnMut(&'static str, &[&'static str]) -> *const __gl_imports::raw::c_void) -> {api} {{
-                
-                {api} {{
-                    ptrs: {api}FnPtrs {{",
-                  api = generators::gen_struct_name(registry.api)));
-
+/// Writes one `field: FnPtr::new(metaloadfn(\"symbol\", &[fallbacks])),` line per command,
+/// for populating a `FnPtrs`-shaped value (however the caller has named its fields) from
+/// within a `load_with`. Expects the `metaloadfn` closure from `write_metaloadfn_prelude` to
+/// be in scope.
+pub(crate) fn write_loaded_fn_ptrs<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
     for cmd in &registry.cmds {
         try!(writeln!(
             dest,
@@ -250,6 +248,41 @@ where
         ))
     }
 
+    Ok(())
+}
+
+/// Creates the `impl` of the structure created by `write_struct`.
+fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    try!(writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol using a custom load function. This allows for the
+            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{",
+                  api = generators::gen_struct_name(registry.api)));
+
+    try!(write_metaloadfn_prelude(dest));
+
+    try!(writeln!(dest,
+                  "                {api}::load_with_metaloadfn(&mut metaloadfn)
+            }}
+
+            #[inline(never)]
+            fn load_with_metaloadfn(metaloadfn: &mut FnMut(&'static str, &[&'static str]) -> *const __gl_imports::raw::c_void) -> {api} {{
+
+                {api} {{
+                    ptrs: {api}FnPtrs {{",
+                  api = generators::gen_struct_name(registry.api)));
+
+    try!(write_loaded_fn_ptrs(registry, dest));
+
     writeln!(dest, "}},")?;
 
     try!(writeln!(dest, "_priv: ()"));
@@ -270,7 +303,7 @@ where
             name = cmd.proto.ident,
             params = gen_parameters(cmd, &registry, true, true).join(", "),
             typed_params = gen_parameters(cmd, &registry, false, true).join(", "),
-            return_suffix = cmd.proto.ty,
+            return_suffix = gen_return_type(cmd, &registry),
             idents = gen_parameters(cmd, &registry, true, false).join(", "),
         ))
     }
@@ -284,19 +317,166 @@ where
     )
 }
 
-fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+pub(crate) fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
     where W: io::Write
 {
+    writeln!(dest, "
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub struct UnknownEnumValue(pub types::GLenum);
+
+        impl ::std::fmt::Display for UnknownEnumValue {{
+            fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{
+                write!(fmt, \"unknown GL enum value: {{:#X}}\", self.0)
+            }}
+        }}
+
+        impl ::std::error::Error for UnknownEnumValue {{}}
+    ")?;
+    writeln!(dest, "")?;
+
     writeln!(dest, "macro_rules! impl_enum_traits {{
-        ($Name:ident) => {{
+        ($Name:ident, $Ty:ty $(, $cname:ident)* $(,)*) => {{
+            impl ::std::convert::TryFrom<$Ty> for $Name {{
+                type Error = UnknownEnumValue;
+
+                #[allow(unreachable_code)]
+                fn try_from(value: $Ty) -> Result<$Name, UnknownEnumValue> {{
+                    $(if value == $Name::$cname.0 {{ return Ok($Name::$cname); }})*
+                    Err(UnknownEnumValue(value as types::GLenum))
+                }}
+            }}
+
+            impl $Name {{
+                #[allow(dead_code)]
+                pub fn variants() -> &'static [$Name] {{
+                    static VARIANTS: &'static [$Name] = &[$($Name::$cname),*];
+                    VARIANTS
+                }}
+            }}
 
+            impl ::std::fmt::Display for $Name {{
+                fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{
+                    match *self {{
+                        $($Name::$cname => write!(fmt, stringify!($cname)),)*
+                        _ => write!(fmt, \"{{:#X}}\", self.0),
+                    }}
+                }}
+            }}
         }}
     }}")?;
     writeln!(dest, "")?;
 
     writeln!(dest, "macro_rules! impl_enum_bitmask_traits {{
-        ($Name:ident) => {{
-            
+        ($Name:ident $(, $cname:ident)* $(,)*) => {{
+            impl $Name {{
+                #[allow(dead_code)]
+                fn all_bits() -> types::GLenum {{
+                    0 $(| $Name::$cname.0)*
+                }}
+
+                #[allow(dead_code)]
+                pub fn contains(self, other: $Name) -> bool {{
+                    (self.0 & other.0) == other.0
+                }}
+
+                #[allow(dead_code)]
+                pub fn intersects(self, other: $Name) -> bool {{
+                    (self.0 & other.0) != 0
+                }}
+
+                #[allow(dead_code)]
+                pub fn is_empty(self) -> bool {{
+                    self.0 == 0
+                }}
+
+                #[allow(dead_code)]
+                pub fn insert(&mut self, other: $Name) {{
+                    self.0 |= other.0;
+                }}
+
+                #[allow(dead_code)]
+                pub fn remove(&mut self, other: $Name) {{
+                    self.0 &= !other.0;
+                }}
+
+                #[allow(dead_code)]
+                pub fn bits(self) -> types::GLenum {{
+                    self.0
+                }}
+
+                #[allow(dead_code)]
+                pub fn from_bits_truncate(bits: types::GLenum) -> $Name {{
+                    $Name(bits & $Name::all_bits())
+                }}
+            }}
+
+            impl ::std::ops::BitOr for $Name {{
+                type Output = $Name;
+                #[inline]
+                fn bitor(self, other: $Name) -> $Name {{
+                    $Name(self.0 | other.0)
+                }}
+            }}
+
+            impl ::std::ops::BitOrAssign for $Name {{
+                #[inline]
+                fn bitor_assign(&mut self, other: $Name) {{
+                    self.0 |= other.0;
+                }}
+            }}
+
+            impl ::std::ops::BitAnd for $Name {{
+                type Output = $Name;
+                #[inline]
+                fn bitand(self, other: $Name) -> $Name {{
+                    $Name(self.0 & other.0)
+                }}
+            }}
+
+            impl ::std::ops::BitAndAssign for $Name {{
+                #[inline]
+                fn bitand_assign(&mut self, other: $Name) {{
+                    self.0 &= other.0;
+                }}
+            }}
+
+            impl ::std::ops::BitXor for $Name {{
+                type Output = $Name;
+                #[inline]
+                fn bitxor(self, other: $Name) -> $Name {{
+                    $Name(self.0 ^ other.0)
+                }}
+            }}
+
+            impl ::std::ops::BitXorAssign for $Name {{
+                #[inline]
+                fn bitxor_assign(&mut self, other: $Name) {{
+                    self.0 ^= other.0;
+                }}
+            }}
+
+            impl ::std::ops::Sub for $Name {{
+                type Output = $Name;
+                #[inline]
+                fn sub(self, other: $Name) -> $Name {{
+                    $Name(self.0 & !other.0)
+                }}
+            }}
+
+            impl ::std::ops::SubAssign for $Name {{
+                #[inline]
+                fn sub_assign(&mut self, other: $Name) {{
+                    self.0 &= !other.0;
+                }}
+            }}
+
+            impl ::std::ops::Not for $Name {{
+                type Output = $Name;
+                #[inline]
+                fn not(self) -> $Name {{
+                    $Name(!self.0 & $Name::all_bits())
+                }}
+            }}
         }}
     }}")?;
     writeln!(dest, "")?;
@@ -314,15 +494,12 @@ fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 
     writeln!(dest, "")?;
     writeln!(dest, "use super::types;")?;
+    writeln!(dest, "use super::UnknownEnumValue;")?;
     writeln!(dest, "")?;
 
     for (_, group) in registry.groups.iter() {
 
-        let enum_type = if group.ident == "Boolean" {
-            "types::GLboolean"
-        } else {
-            "types::GLenum"
-        };
+        let enum_type = group_backing_type(&group.ident);
 
         writeln!(dest, "#[repr(transparent)]")?;
         writeln!(dest, "#[derive(Copy, Clone, PartialEq, Eq, Hash)]")?;
@@ -330,18 +507,22 @@ fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
         writeln!(dest, "")?;
         writeln!(dest, "impl {} {{", group.ident)?;
 
+        let is_bitmask = group.enums_type.as_ref().map(|t| t.as_str()) == Some("bitmask");
+
         let mut group_enums = ::std::collections::HashSet::new();
+        let mut group_enums_ordered = Vec::new();
 
         for enum_name in group.enums.iter() {
             let unique = group_enums.insert(enum_name.as_str());
             if unique && enums.contains(enum_name.as_str()) {
-                writeln!(dest, "    pub const {enum_name}: {group_name} = {group_name}(super::{enum_name});", 
+                writeln!(dest, "    pub const {enum_name}: {group_name} = {group_name}(super::{enum_name});",
                     group_name = group.ident, enum_name = enum_name)?;
+                group_enums_ordered.push(enum_name.as_str());
             }
         }
-        
-        if let Some("bitmask") = group.enums_type.as_ref().map(|t| t.as_str()) {
-            writeln!(dest, "    pub const Empty: {group_name} = {group_name}(0);", 
+
+        if is_bitmask {
+            writeln!(dest, "    pub const Empty: {group_name} = {group_name}(0);",
                 group_name = group.ident)?;
         }
 
@@ -350,24 +531,45 @@ fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
 
         writeln!(dest, "impl ::std::fmt::Debug for {} {{", group.ident)?;
         writeln!(dest, "    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {{")?;
-        writeln!(dest, "        match *self {{")?;
-        for enum_name in group_enums.iter() {
-            if enums.contains(enum_name) {
-                writeln!(dest, "            {group_name}::{enum_name} => write!(fmt, \"{group_name}({enum_name})\"),", 
+
+        if is_bitmask {
+            writeln!(dest, "        let mut remaining = self.0;")?;
+            writeln!(dest, "        let mut parts: Vec<&str> = Vec::new();")?;
+            for enum_name in group_enums_ordered.iter() {
+                writeln!(dest,
+                    "        if remaining & {group_name}::{enum_name}.0 != 0 {{ parts.push(\"{enum_name}\"); remaining &= !{group_name}::{enum_name}.0; }}",
                     group_name = group.ident, enum_name = enum_name)?;
             }
+            writeln!(dest, "        let mut rendered = parts.join(\" | \");")?;
+            writeln!(dest, "        if remaining != 0 {{")?;
+            writeln!(dest, "            if !rendered.is_empty() {{ rendered.push_str(\" | \"); }}")?;
+            writeln!(dest, "            rendered.push_str(&format!(\"{{:#X}}\", remaining));")?;
+            writeln!(dest, "        }}")?;
+            writeln!(dest, "        if rendered.is_empty() {{ rendered.push_str(\"Empty\"); }}")?;
+            writeln!(dest, "        write!(fmt, \"{group_name}({{}})\", rendered)", group_name = group.ident)?;
+        } else {
+            writeln!(dest, "        match *self {{")?;
+            for enum_name in group_enums.iter() {
+                if enums.contains(enum_name) {
+                    writeln!(dest, "            {group_name}::{enum_name} => write!(fmt, \"{group_name}({enum_name})\"),",
+                        group_name = group.ident, enum_name = enum_name)?;
+                }
+            }
+            writeln!(dest, "            _ => write!(fmt, \"{group_name}({{}})\", self.0),", group_name = group.ident)?;
+            writeln!(dest, "        }}")?;
         }
-        writeln!(dest, "            _ => write!(fmt, \"{group_name}({{}})\", self.0),", group_name = group.ident)?;
-        writeln!(dest, "        }}")?;
+
         writeln!(dest, "    }}")?;
         writeln!(dest, "}}")?;
         writeln!(dest, "")?;
 
-        writeln!(dest, "impl_enum_traits!({});", group.ident)?;
+        writeln!(dest, "impl_enum_traits!({}, {}{});", group.ident, enum_type,
+            group_enums_ordered.iter().map(|n| format!(", {}", n)).collect::<String>())?;
         writeln!(dest, "")?;
 
-        if let Some("bitmask") = group.enums_type.as_ref().map(|t| t.as_str()) {
-            writeln!(dest, "impl_enum_bitmask_traits!({});", group.ident)?;
+        if is_bitmask {
+            writeln!(dest, "impl_enum_bitmask_traits!({}{});", group.ident,
+                group_enums_ordered.iter().map(|n| format!(", {}", n)).collect::<String>())?;
             writeln!(dest, "")?;
         }
     }
@@ -377,15 +579,31 @@ fn write_enum_groups<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
     Ok(())
 }
 
+/// The Rust type backing an enum group's newtype: `types::GLboolean` for the special
+/// `Boolean` group (`GL_TRUE`/`GL_FALSE`), `types::GLenum` for every other group.
+pub(crate) fn group_backing_type(group_ident: &str) -> &'static str {
+    if group_ident == "Boolean" {
+        "types::GLboolean"
+    } else {
+        "types::GLenum"
+    }
+}
+
 /// Generates the list of Rust `Arg`s that a `Cmd` requires.
+///
+/// A parameter is only given its enum-group newtype when the parameter's own raw type is
+/// exactly the group's backing type (a plain `GLenum`/`GLboolean` value, not a pointer or
+/// array out-param, and not some other integer type the group doesn't actually back) — the
+/// newtype is `#[repr(transparent)]` over that backing type, so substituting it anywhere else
+/// would silently pass the wrong ABI.
 pub fn gen_parameters(cmd: &Cmd, registry: &Registry, with_idents: bool, with_types: bool) -> Vec<String> {
     cmd.params
         .iter()
         .map(|binding| {
-            let ty = binding.group
-                .as_ref()
-                .and_then(|group| registry.groups.get(group).map(|group| format!("enums::{}", group.ident)))
-                .unwrap_or(binding.ty.to_string());
+            let ty = match binding.group.as_ref().and_then(|group| registry.groups.get(group)) {
+                Some(group) if binding.ty == group_backing_type(&group.ident) => format!("enums::{}", group.ident),
+                _ => binding.ty.to_string(),
+            };
 
             // returning
             if with_idents && with_types {
@@ -399,4 +617,15 @@ pub fn gen_parameters(cmd: &Cmd, registry: &Registry, with_idents: bool, with_ty
             }
         })
         .collect()
+}
+
+/// Generates the Rust return type for `cmd`, mapping its `group` (if any, and present in
+/// `registry.groups`) to the corresponding `enums::<Group>` newtype. Unlike `gen_parameters`,
+/// the raw return type is not compared against the group's backing type.
+pub(crate) fn gen_return_type(cmd: &Cmd, registry: &Registry) -> String {
+    cmd.proto
+        .group
+        .as_ref()
+        .and_then(|group| registry.groups.get(group).map(|group| format!("enums::{}", group.ident)))
+        .unwrap_or(cmd.proto.ty.to_string())
 }
\ No newline at end of file